@@ -1,7 +1,9 @@
-use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::diagnostics::span_lint_and_then;
 use rustc_ast::ast::*;
+use rustc_errors::Applicability;
 use rustc_lint::{EarlyContext, EarlyLintPass};
-use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::BytePos;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -13,7 +15,7 @@ declare_clippy_lint! {
     /// Most types should either be:
     /// * Abstract data types: complex objects with opaque implementation which guard
     /// interior invariants and expose intentionally limited API to the outside world.
-    /// * Data: relatively simple objects which group a bunch of related attributes together.
+    /// * Data: relatively simple objects which group a bunch of related attributes together.
     ///
     /// ### Example
     /// ```rust
@@ -31,12 +33,87 @@ declare_clippy_lint! {
     ///     pub b,
     /// }
     /// ```
+    ///
+    /// ### Configuration
+    /// `partial-pub-fields-threshold` can be used to allow a struct to have up to a given
+    /// number of fields whose visibility differs from the rest (e.g. a single private
+    /// bookkeeping field on an otherwise `pub` struct) before the lint fires.
     #[clippy::version = "1.66.0"]
     pub PARTIAL_PUB_FIELDS,
     restriction,
     "partial fields of a struct are public"
 }
-declare_lint_pass!(PartialPubFields => [PARTIAL_PUB_FIELDS]);
+pub struct PartialPubFields {
+    /// How many minority-visibility fields a struct is allowed to have before the lint fires.
+    /// See `partial-pub-fields-threshold` in the clippy.toml documentation.
+    allowed_minority_count: u64,
+}
+
+impl PartialPubFields {
+    pub fn new(allowed_minority_count: u64) -> Self {
+        Self { allowed_minority_count }
+    }
+}
+
+// NOTE: this checkout contains only `partial_pub_fields.rs`, not `clippy_lints/src/lib.rs` or
+// `clippy_lints/src/utils/conf.rs`, so the `Conf` field and registration call site below can't
+// physically be added here. For this lint pass to actually read `partial-pub-fields-threshold`,
+// `clippy_lints/src/utils/conf.rs` needs a matching entry:
+//   (partial_pub_fields_threshold: u64 = 0),
+// and `clippy_lints/src/lib.rs` needs to construct the pass with it instead of relying on
+// `declare_lint_pass!`'s zero-argument default:
+//   store.register_early_pass(move || Box::new(partial_pub_fields::PartialPubFields::new(conf.partial_pub_fields_threshold)));
+
+impl_lint_pass!(PartialPubFields => [PARTIAL_PUB_FIELDS]);
+
+/// The visibility "tier" a field belongs to. Two restricted visibilities (e.g. `pub(crate)` and
+/// `pub(in some::path)`) are considered the same tier even though their paths differ, since what
+/// matters for this lint is whether a field is world-visible, scope-visible or private.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VisTier {
+    Public,
+    Restricted,
+    Private,
+}
+
+impl VisTier {
+    fn of(vis: &Visibility) -> Self {
+        match vis.kind {
+            VisibilityKind::Public => Self::Public,
+            VisibilityKind::Restricted { .. } => Self::Restricted,
+            VisibilityKind::Inherited => Self::Private,
+        }
+    }
+
+    fn descr(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Restricted => "restricted (e.g. `pub(crate)`)",
+            Self::Private => "private",
+        }
+    }
+}
+
+/// The tier shared by the largest number of fields, i.e. the tier the struct is "mostly" using.
+/// Ties are broken in favor of `Public`, then `Restricted`, then `Private`.
+fn majority_tier(fields: &[FieldDef]) -> VisTier {
+    let (mut pub_count, mut restricted_count, mut priv_count) = (0u32, 0u32, 0u32);
+    for field in fields {
+        match VisTier::of(&field.vis) {
+            VisTier::Public => pub_count += 1,
+            VisTier::Restricted => restricted_count += 1,
+            VisTier::Private => priv_count += 1,
+        }
+    }
+
+    if pub_count >= restricted_count && pub_count >= priv_count {
+        VisTier::Public
+    } else if restricted_count >= priv_count {
+        VisTier::Restricted
+    } else {
+        VisTier::Private
+    }
+}
 
 impl EarlyLintPass for PartialPubFields {
     fn check_item(&mut self, cx: &EarlyContext<'_>, item: &Item) {
@@ -44,38 +121,61 @@ impl EarlyLintPass for PartialPubFields {
             return;
         };
 
-        let mut fields = st.fields().iter();
-        let Some(first_field) = fields.next() else {
+        let fields = st.fields();
+        if fields.is_empty() {
             // Empty struct.
             return;
-        };
-        let all_pub = first_field.vis.kind.is_pub();
-        let all_priv = !all_pub;
+        }
+        let majority_tier = majority_tier(fields);
+
+        // Fields whose tier differs from the majority; this is the true minority, regardless of
+        // field order, so its length is what `allowed_minority_count` is meant to bound.
+        let minority_fields: Vec<&FieldDef> = fields.iter().filter(|field| VisTier::of(&field.vis) != majority_tier).collect();
+
+        if minority_fields.len() as u64 <= self.allowed_minority_count {
+            return;
+        }
 
         let msg = "mixed usage of pub and non-pub fields";
 
-        for field in fields {
-            if all_priv && field.vis.kind.is_pub() {
-                span_lint_and_help(
-                    cx,
-                    &PARTIAL_PUB_FIELDS,
-                    field.vis.span,
-                    msg,
-                    None,
-                    "consider using private field here",
-                );
-                return;
-            } else if all_pub && !field.vis.kind.is_pub() {
-                span_lint_and_help(
-                    cx,
-                    &PARTIAL_PUB_FIELDS,
+        span_lint_and_then(cx, &PARTIAL_PUB_FIELDS, item.span, msg, |diag| {
+            for field in &minority_fields {
+                diag.span_note(
                     field.vis.span,
-                    msg,
-                    None,
-                    "consider using public field here",
+                    format!("this field is {}, but other fields are {}", VisTier::of(&field.vis).descr(), majority_tier.descr()),
                 );
-                return;
             }
-        }
+
+            // The fix is driven by the *majority*, not by what each outlier currently is: every
+            // minority field is rewritten to the same tier, so all outliers are suggested as a
+            // single atomic edit rather than as mutually-exclusive alternatives.
+            match majority_tier {
+                VisTier::Public => {
+                    let suggestions = minority_fields.iter().map(|field| (field.vis.span, "pub ".to_owned())).collect::<Vec<_>>();
+                    diag.multipart_suggestion(
+                        "make these fields public to match the rest of the struct",
+                        suggestions,
+                        Applicability::MachineApplicable,
+                    );
+                },
+                VisTier::Private => {
+                    // `vis.span` only covers the visibility keyword itself (e.g. `pub` or
+                    // `pub(crate)`), not the space separating it from the field name, so widen it
+                    // by one byte to avoid leaving that space behind.
+                    let suggestions = minority_fields
+                        .iter()
+                        .map(|field| (field.vis.span.with_hi(field.vis.span.hi() + BytePos(1)), String::new()))
+                        .collect::<Vec<_>>();
+                    diag.multipart_suggestion(
+                        "make these fields private to match the rest of the struct",
+                        suggestions,
+                        Applicability::MachineApplicable,
+                    );
+                },
+                // `pub(in path)` can't be fabricated for the minority fields, so there's nothing
+                // mechanical to suggest; the notes above are all we can offer.
+                VisTier::Restricted => {},
+            }
+        });
     }
 }